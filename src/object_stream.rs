@@ -1,30 +1,256 @@
 use crate::parser::{self, ParserInput};
-use crate::{dictionary, Document, Error, Object, ObjectId, Result, Stream};
+use crate::{dictionary, Dictionary, Document, Error, Object, ObjectId, Result, Stream};
 use std::collections::{BTreeMap, HashSet};
 use std::num::TryFromIntError;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use log::warn;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
+/// The filter (or chain of filters) used to encode the content of an object stream.
+///
+/// A PDF `/Filter` array is applied left to right *on read*: each filter decodes the result of
+/// the one before it, so the first array entry is the outermost layer actually written to the
+/// stream. `Chain` therefore applies its filters right to left *on write* — the last filter
+/// first (innermost), the first filter last (outermost) — so that reading them back in array
+/// order undoes exactly what was written. E.g. `[ASCII85Decode, FlateDecode]` means "Flate-encode,
+/// then ASCII85-encode on write; ASCII85-decode, then Flate-decode on read".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    FlateDecode,
+    LZWDecode,
+    RunLengthDecode,
+    ASCII85Decode,
+    ASCIIHexDecode,
+    Chain(Vec<Filter>),
+}
+
+impl Filter {
+    /// This filter's PDF filter name, or an error if called on a `Chain` (a chain has no single
+    /// name; only the concrete filters inside it do).
+    fn pdf_name(&self) -> Result<&'static [u8]> {
+        match self {
+            Filter::FlateDecode => Ok(b"FlateDecode"),
+            Filter::LZWDecode => Ok(b"LZWDecode"),
+            Filter::RunLengthDecode => Ok(b"RunLengthDecode"),
+            Filter::ASCII85Decode => Ok(b"ASCII85Decode"),
+            Filter::ASCIIHexDecode => Ok(b"ASCIIHexDecode"),
+            Filter::Chain(_) => Err(Error::InvalidObjectStream(
+                "nested filter chains are not supported".into(),
+            )),
+        }
+    }
+
+    /// The `/DecodeParms` entry for this filter, or `Object::Null` when it needs none.
+    fn decode_parms(&self) -> Object {
+        match self {
+            Filter::LZWDecode => dictionary! { "EarlyChange" => 1 }.into(),
+            _ => Object::Null,
+        }
+    }
+
+    /// Write this filter's `/Filter` (and `/DecodeParms`, where applicable) entries into `dict`.
+    /// Errors if `self` (or, for a `Chain`, one of its members) is a nested `Chain`.
+    fn write_dict_entries(&self, dict: &mut Dictionary) -> Result<()> {
+        match self {
+            Filter::Chain(filters) => {
+                let mut names = Vec::with_capacity(filters.len());
+                let mut parms = Vec::with_capacity(filters.len());
+                for filter in filters {
+                    names.push(Object::Name(filter.pdf_name()?.to_vec()));
+                    parms.push(filter.decode_parms());
+                }
+
+                dict.set("Filter", Object::Array(names));
+                if parms.iter().any(|parm| !matches!(parm, Object::Null)) {
+                    dict.set("DecodeParms", Object::Array(parms));
+                }
+            }
+            single => {
+                dict.set("Filter", Object::Name(single.pdf_name()?.to_vec()));
+                let parms = single.decode_parms();
+                if !matches!(parms, Object::Null) {
+                    dict.set("DecodeParms", parms);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode `content` through this filter (or filter chain). `compression_level` is only
+    /// consulted for `FlateDecode` steps.
+    fn encode(&self, content: &[u8], compression_level: u32) -> Result<Vec<u8>> {
+        match self {
+            Filter::Chain(filters) => filters
+                .iter()
+                .rev()
+                .try_fold(content.to_vec(), |buf, filter| filter.encode(&buf, compression_level)),
+            Filter::FlateDecode => {
+                use flate2::write::ZlibEncoder;
+                use flate2::Compression;
+                use std::io::prelude::*;
+
+                // Pass the 0-9 level straight through to zlib instead of collapsing it into
+                // coarse buckets, so every level is distinguishable.
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(compression_level));
+                encoder.write_all(content)?;
+                Ok(encoder.finish()?)
+            }
+            Filter::LZWDecode => {
+                // PDF's LZWDecode defaults to `/EarlyChange 1` (see `decode_parms` above), the
+                // TIFF-style early code-width bump; delegate to `weezl` rather than hand-rolling
+                // the variable-width bit packing, which is easy to get subtly wrong around the
+                // code-width transition points.
+                let mut encoder = weezl::encode::Encoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8);
+                encoder
+                    .encode(content)
+                    .map_err(|e| Error::InvalidObjectStream(format!("LZW encode failed: {e}")))
+            }
+            Filter::RunLengthDecode => Ok(run_length_encode(content)),
+            Filter::ASCII85Decode => Ok(ascii85_encode(content)),
+            Filter::ASCIIHexDecode => Ok(ascii_hex_encode(content)),
+        }
+    }
+}
+
+/// Encode `data` with the PDF `RunLengthDecode` filter's run-length scheme.
+fn run_length_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let mut run_len = 1;
+        while run_len < 128 && i + run_len < data.len() && data[i + run_len] == data[i] {
+            run_len += 1;
+        }
+
+        if run_len >= 2 {
+            out.push((257 - run_len) as u8);
+            out.push(data[i]);
+            i += run_len;
+        } else {
+            let start = i;
+            let mut len = 1;
+            i += 1;
+            while len < 128 && i < data.len() && !(i + 1 < data.len() && data[i] == data[i + 1]) {
+                len += 1;
+                i += 1;
+            }
+            out.push((len - 1) as u8);
+            out.extend_from_slice(&data[start..start + len]);
+        }
+    }
+    out.push(128);
+    out
+}
+
+/// Encode `data` with the PDF `ASCII85Decode` filter's base-85 scheme.
+fn ascii85_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in data.chunks(4) {
+        if chunk.len() == 4 && chunk == [0, 0, 0, 0] {
+            out.push(b'z');
+            continue;
+        }
+
+        let mut buf = [0u8; 4];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(buf);
+
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = (value % 85) as u8;
+            value /= 85;
+        }
+
+        for d in &digits[..chunk.len() + 1] {
+            out.push(d + b'!');
+        }
+    }
+    out.extend_from_slice(b"~>");
+    out
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Encode `data` with the PDF `ASCIIHexDecode` filter.
+fn ascii_hex_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 2 + 1);
+    for byte in data {
+        out.push(HEX_DIGITS[(byte >> 4) as usize]);
+        out.push(HEX_DIGITS[(byte & 0x0F) as usize]);
+    }
+    out.push(b'>');
+    out
+}
+
 #[derive(Debug)]
 pub struct ObjectStream {
     pub objects: BTreeMap<ObjectId, Object>,
     max_objects: usize,
     compression_level: u32,
+    filter: Filter,
+    stored: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ObjectStreamBuilder {
     max_objects: usize,
     compression_level: u32,
+    filter: Filter,
+    stored: bool,
+}
+
+/// Parse an object stream's `/First`/`/N` header and index table (the whitespace-separated
+/// `object_number offset` pairs stored before `/First`) into `(first_offset, entries)`, where
+/// each entry is `(object_number, absolute_offset_into_content)`. Shared by [`ObjectStream::new`]
+/// and [`LazyObjectStream::new`], which otherwise parse identical bytes into different shapes.
+fn parse_index_table(stream: &Stream) -> Result<(usize, Vec<(u32, usize)>)> {
+    let first_offset: usize = stream
+        .dict
+        .get(b"First")
+        .and_then(Object::as_i64)?
+        .try_into()
+        .map_err(|e: TryFromIntError| Error::NumericCast(e.to_string()))?;
+    let index_block = stream
+        .content
+        .get(..first_offset)
+        .ok_or(Error::InvalidOffset(first_offset))?;
+
+    let numbers_str = std::str::from_utf8(index_block).map_err(|e| Error::InvalidObjectStream(e.to_string()))?;
+    let numbers: Vec<_> = numbers_str
+        .split_whitespace()
+        .map(|number| u32::from_str(number).ok())
+        .collect();
+    let len = numbers.len() / 2 * 2; // Ensure only pairs.
+
+    let n = stream.dict.get(b"N").and_then(Object::as_i64)?;
+    if numbers.len().try_into().ok() != n.checked_mul(2) {
+        warn!("object stream: the object stream dictionary specifies a wrong number of objects")
+    }
+
+    let entries = numbers[..len]
+        .chunks(2)
+        .filter_map(|chunk| {
+            let id = chunk[0]?;
+            let offset = first_offset + chunk[1]? as usize;
+            if offset >= stream.content.len() {
+                warn!("out-of-bounds offset in object stream");
+                return None;
+            }
+            Some((id, offset))
+        })
+        .collect();
+
+    Ok((first_offset, entries))
 }
 
 #[derive(Debug, Clone)]
 pub struct ObjectStreamConfig {
     pub max_objects_per_stream: usize,
     pub compression_level: u32,
+    pub filter: Filter,
 }
 
 impl Default for ObjectStreamConfig {
@@ -32,6 +258,7 @@ impl Default for ObjectStreamConfig {
         Self {
             max_objects_per_stream: 100,
             compression_level: 6,
+            filter: Filter::FlateDecode,
         }
     }
 }
@@ -46,53 +273,28 @@ impl ObjectStream {
                 objects: BTreeMap::new(),
                 max_objects: 100,
                 compression_level: 6,
+                filter: Filter::FlateDecode,
+                stored: false,
             });
         }
 
-        let first_offset = stream
-            .dict
-            .get(b"First")
-            .and_then(Object::as_i64)?
-            .try_into()
-            .map_err(|e: TryFromIntError| Error::NumericCast(e.to_string()))?;
-        let index_block = stream
-            .content
-            .get(..first_offset)
-            .ok_or(Error::InvalidOffset(first_offset))?;
-
-        let numbers_str = std::str::from_utf8(index_block).map_err(|e| Error::InvalidObjectStream(e.to_string()))?;
-        let numbers: Vec<_> = numbers_str
-            .split_whitespace()
-            .map(|number| u32::from_str(number).ok())
-            .collect();
-        let len = numbers.len() / 2 * 2; // Ensure only pairs.
-
-        let n = stream.dict.get(b"N").and_then(Object::as_i64)?;
-        if numbers.len().try_into().ok() != n.checked_mul(2) {
-            warn!("object stream: the object stream dictionary specifies a wrong number of objects")
-        }
+        let (_, entries) = parse_index_table(stream)?;
 
-        let chunks_filter_map = |chunk: &[_]| {
-            let id = chunk[0]?;
-            let offset = first_offset + chunk[1]? as usize;
-
-            if offset >= stream.content.len() {
-                warn!("out-of-bounds offset in object stream");
-                return None;
-            }
+        let parse_entry = |&(id, offset): &(u32, usize)| {
             let object = parser::direct_object(ParserInput::new_extra(&stream.content[offset..], "direct object"))?;
-
             Some(((id, 0), object))
         };
         #[cfg(feature = "rayon")]
-        let objects = numbers[..len].par_chunks(2).filter_map(chunks_filter_map).collect();
+        let objects = entries.par_iter().filter_map(parse_entry).collect();
         #[cfg(not(feature = "rayon"))]
-        let objects = numbers[..len].chunks(2).filter_map(chunks_filter_map).collect();
+        let objects = entries.iter().filter_map(parse_entry).collect();
 
-        Ok(ObjectStream { 
+        Ok(ObjectStream {
             objects,
             max_objects: 100,
             compression_level: 6,
+            filter: Filter::FlateDecode,
+            stored: false,
         })
     }
 
@@ -101,6 +303,8 @@ impl ObjectStream {
         ObjectStreamBuilder {
             max_objects: 100,
             compression_level: 6,
+            filter: Filter::FlateDecode,
+            stored: false,
         }
     }
 
@@ -203,27 +407,13 @@ impl ObjectStream {
         };
 
         let mut stream = Stream::new(dict, content);
-        
-        // Apply compression - object streams should always be compressed
-        if self.compression_level > 0 {
-            // Force compression by setting Filter directly
-            use flate2::write::ZlibEncoder;
-            use flate2::Compression;
-            use std::io::prelude::*;
-            
-            let compression = match self.compression_level {
-                0 => Compression::none(),
-                1..=3 => Compression::fast(),
-                4..=6 => Compression::default(),
-                _ => Compression::best(),
-            };
-            
-            let mut encoder = ZlibEncoder::new(Vec::new(), compression);
-            encoder.write_all(&stream.content)?;
-            let compressed = encoder.finish()?;
-            
-            stream.dict.set("Filter", "FlateDecode");
-            stream.set_content(compressed);
+
+        // `stored` leaves the content verbatim and writes no `/Filter`, for debugging and for
+        // content that doesn't benefit from compression. Otherwise apply the configured filter.
+        if !self.stored {
+            let encoded = self.filter.encode(&stream.content, self.compression_level)?;
+            self.filter.write_dict_entries(&mut stream.dict)?;
+            stream.set_content(encoded);
         }
 
         Ok(stream)
@@ -336,6 +526,63 @@ impl ObjectStream {
     }
 }
 
+/// A decompressed-once, parsed-on-demand view of an object stream.
+///
+/// Unlike [`ObjectStream::new`], which eagerly parses every contained object into a
+/// `BTreeMap`, `LazyObjectStream` decompresses the stream a single time and keeps only the
+/// index table (object number -> byte offset). Call [`LazyObjectStream::get`] to parse a
+/// single object out of the buffer on demand; the underlying buffer is shared (not copied)
+/// across calls.
+#[derive(Debug)]
+pub struct LazyObjectStream {
+    content: Arc<[u8]>,
+    /// `(object_number, offset_into_content)`, sorted by offset.
+    index: Vec<(u32, usize)>,
+}
+
+impl LazyObjectStream {
+    /// Parse an existing object stream's header, without parsing any of its objects.
+    pub fn new(stream: &mut Stream) -> Result<LazyObjectStream> {
+        let _ = stream.decompress();
+
+        if stream.content.is_empty() {
+            return Ok(LazyObjectStream {
+                content: Arc::from(Vec::new().into_boxed_slice()),
+                index: Vec::new(),
+            });
+        }
+
+        let (_, mut index) = parse_index_table(stream)?;
+        index.sort_by_key(|&(_, offset)| offset);
+
+        Ok(LazyObjectStream {
+            content: Arc::from(stream.content.clone().into_boxed_slice()),
+            index,
+        })
+    }
+
+    /// The number of objects indexed in this stream.
+    pub fn object_count(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Parse and return the object with the given id, decoding only its slice of the buffer.
+    pub fn get(&self, id: ObjectId) -> Result<Object> {
+        let pos = self
+            .index
+            .iter()
+            .position(|&(number, _)| number == id.0)
+            .ok_or_else(|| Error::InvalidObjectStream(format!("object {} not found in object stream", id.0)))?;
+
+        let (_, start) = self.index[pos];
+        let end = self.index.get(pos + 1).map(|&(_, next)| next).unwrap_or(self.content.len());
+
+        let slice = self.content.get(start..end).ok_or(Error::InvalidOffset(start))?;
+        parser::direct_object(ParserInput::new_extra(slice, "direct object"))
+            .ok_or_else(|| Error::InvalidObjectStream(format!("failed to parse object {} in object stream", id.0)))
+    }
+}
+
 impl ObjectStreamBuilder {
     /// Set the maximum number of objects per stream
     pub fn max_objects(mut self, max: usize) -> Self {
@@ -343,9 +590,26 @@ impl ObjectStreamBuilder {
         self
     }
 
-    /// Set the compression level (0-9)
+    /// Set the compression level (0-9), passed straight through to the underlying codec.
+    /// Out-of-range values are clamped to 9, matching zlib's own behavior for invalid levels.
     pub fn compression_level(mut self, level: u32) -> Self {
-        self.compression_level = level;
+        if level > 9 {
+            warn!("object stream: compression_level {level} out of range 0..=9, clamping to 9");
+        }
+        self.compression_level = level.min(9);
+        self.stored = false;
+        self
+    }
+
+    /// Set the filter (or filter chain) used to encode the stream's content
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Store the content verbatim: no filter is applied and no `/Filter` entry is written
+    pub fn stored(mut self) -> Self {
+        self.stored = true;
         self
     }
 
@@ -355,6 +619,8 @@ impl ObjectStreamBuilder {
             objects: BTreeMap::new(),
             max_objects: self.max_objects,
             compression_level: self.compression_level,
+            filter: self.filter,
+            stored: self.stored,
         }
     }
 
@@ -367,4 +633,203 @@ impl ObjectStreamBuilder {
     pub fn get_compression_level(&self) -> u32 {
         self.compression_level
     }
+
+    /// Get the current filter setting
+    pub fn get_filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    /// Whether this builder is set to store content verbatim
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+}
+
+/// Packing statistics for a single object stream produced by [`Document::pack_into_object_streams`].
+#[derive(Debug, Clone)]
+pub struct ObjectStreamChunk {
+    /// The id of the `ObjStm` stream itself.
+    pub stream_id: ObjectId,
+    /// The lowest object number packed into this stream.
+    pub first_object_number: u32,
+    /// How many objects were packed into this stream.
+    pub object_count: usize,
+    /// Size of the stream's content before encoding.
+    pub uncompressed_size: usize,
+    /// Size of the stream's content after encoding.
+    pub compressed_size: usize,
+    /// The ids of the objects packed into this stream, in the order they were written, so
+    /// `object_ids[i]`'s `index_within_stream` is `i`. A future xref-stream writer can zip this
+    /// against `stream_id` to emit `Compressed` entries instead of direct offsets.
+    pub object_ids: Vec<ObjectId>,
+}
+
+impl Document {
+    /// Bin-pack this document's compressible objects into one or more object streams.
+    ///
+    /// Objects that cannot be compressed (streams, `Page`/`Pages`/`Catalog`/`XRef`/`ObjStm`
+    /// dictionaries, and anything transitively reachable from them or from the trailer, per
+    /// [`ObjectStream::can_be_compressed`]) are left untouched. The rest are bin-packed into
+    /// `ceil(N / max_objects_per_stream)` object streams, each built and compressed
+    /// independently (in parallel, under the `rayon` feature) using `config`, and added to the
+    /// document as new indirect stream objects. Returns one [`ObjectStreamChunk`] per stream
+    /// produced, in stream order, so callers can inspect packing efficiency and (via
+    /// `object_ids`) recover each packed object's `index_within_stream`.
+    ///
+    /// Note: this repo's writer/xref layer doesn't yet have a `Compressed` cross-reference entry
+    /// type pointing at `(stream_id, index_within_stream)`, so it has no way to omit the
+    /// now-redundant direct objects from the saved file. Until that support lands, this
+    /// deliberately leaves the original direct objects in `self.objects` alongside the new
+    /// `ObjStm`s — every indirect reference elsewhere in the document still resolves via
+    /// `Document::get_object` exactly as before. That means this pass does not yet shrink the
+    /// document; it only produces the object streams themselves, ready for a future writer to
+    /// switch over to once it can emit `Compressed` xref entries.
+    pub fn pack_into_object_streams(&mut self, config: ObjectStreamConfig) -> Result<Vec<ObjectStreamChunk>> {
+        let non_compressible = ObjectStream::find_all_non_compressible_objects(self);
+
+        let mut compressible: Vec<ObjectId> = self
+            .objects
+            .keys()
+            .copied()
+            .filter(|id| !non_compressible.contains(id))
+            .collect();
+        compressible.sort();
+
+        if compressible.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let max_objects_per_stream = config.max_objects_per_stream.max(1);
+        let mut next_object_number = self.objects.keys().map(|&(number, _)| number).max().unwrap_or(0) + 1;
+
+        let batches: Vec<(ObjectId, Vec<ObjectId>)> = compressible
+            .chunks(max_objects_per_stream)
+            .map(|batch| {
+                let stream_id = (next_object_number, 0);
+                next_object_number += 1;
+                (stream_id, batch.to_vec())
+            })
+            .collect();
+
+        let pack_one = |(stream_id, batch): &(ObjectId, Vec<ObjectId>)| -> Result<(ObjectId, ObjectStreamChunk, Stream)> {
+            let mut object_stream = ObjectStream::builder()
+                .max_objects(batch.len())
+                .compression_level(config.compression_level)
+                .filter(config.filter.clone())
+                .build();
+
+            for &id in batch {
+                let obj = self
+                    .objects
+                    .get(&id)
+                    .cloned()
+                    .ok_or_else(|| Error::InvalidObjectStream(format!("object {} missing while packing", id.0)))?;
+                object_stream.add_object(id, obj)?;
+            }
+
+            let uncompressed_size = object_stream.build_stream_content()?.len();
+            let stream = object_stream.to_stream_object()?;
+            let chunk = ObjectStreamChunk {
+                stream_id: *stream_id,
+                first_object_number: batch[0].0,
+                object_count: batch.len(),
+                uncompressed_size,
+                compressed_size: stream.content.len(),
+                object_ids: batch.clone(),
+            };
+
+            Ok((*stream_id, chunk, stream))
+        };
+
+        #[cfg(feature = "rayon")]
+        let packed: Vec<_> = batches.par_iter().map(pack_one).collect::<Result<_>>()?;
+        #[cfg(not(feature = "rayon"))]
+        let packed: Vec<_> = batches.iter().map(pack_one).collect::<Result<_>>()?;
+
+        let mut chunks = Vec::with_capacity(packed.len());
+        for (stream_id, chunk, stream) in packed {
+            self.objects.insert(stream_id, Object::Stream(stream));
+            chunks.push(chunk);
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn lzw_round_trips_across_code_width_transitions() {
+        // Slowly repeating content (a 251-byte cycle) long enough to cross the 9->10 and 10->11 bit code-width
+        // boundaries, verified against weezl's independent LZW decoder.
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let encoded = Filter::LZWDecode.encode(&data, 0).expect("lzw encode");
+
+        // `with_tiff_size_switch` matches the `/EarlyChange 1` we always declare in `decode_parms`.
+        let mut decoder = weezl::decode::Decoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8);
+        let decoded = decoder.decode(&encoded).expect("lzw decode");
+
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn chain_encodes_innermost_filter_first() {
+        // `[LZWDecode, FlateDecode]` means, on read, LZW-decode first then Flate-decode; so on
+        // write the correct order is the reverse: Flate-encode first, then LZW-encode the
+        // result. Decode independently (flate2, weezl) in array order to confirm the bytes
+        // Chain produced are consistent with that declared order, not the reverse.
+        let data: Vec<u8> = (0..5_000u32).map(|i| (i % 200) as u8).collect();
+        let chain = Filter::Chain(vec![Filter::LZWDecode, Filter::FlateDecode]);
+        let encoded = chain.encode(&data, 6).expect("chain encode");
+
+        let mut lzw_decoder = weezl::decode::Decoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8);
+        let flate_compressed = lzw_decoder.decode(&encoded).expect("lzw decode");
+
+        let mut decoded = Vec::new();
+        flate2::read::ZlibDecoder::new(&flate_compressed[..])
+            .read_to_end(&mut decoded)
+            .expect("flate decode");
+
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn lazy_object_stream_round_trips_every_object() {
+        // `stored()` keeps the content verbatim, so this exercises LazyObjectStream::new's
+        // First/N/index-table parsing (shared with ObjectStream::new via parse_index_table)
+        // without also depending on a particular filter's decode path.
+        let mut object_stream = ObjectStream::builder().stored().build();
+        let entries = [
+            ((1, 0), Object::Integer(42)),
+            ((2, 0), Object::Name(b"Foo".to_vec())),
+            ((5, 0), Object::Array(vec![Object::Integer(1), Object::Integer(2)])),
+        ];
+        for (id, obj) in &entries {
+            object_stream.add_object(*id, obj.clone()).expect("add_object");
+        }
+
+        let mut stream = object_stream.to_stream_object().expect("to_stream_object");
+        let lazy = LazyObjectStream::new(&mut stream).expect("LazyObjectStream::new");
+
+        assert_eq!(lazy.object_count(), entries.len());
+        for (id, obj) in &entries {
+            assert_eq!(&lazy.get(*id).expect("get"), obj);
+        }
+    }
+
+    #[test]
+    fn stored_writes_no_filter_entry_and_keeps_content_verbatim() {
+        let mut object_stream = ObjectStream::builder().stored().build();
+        object_stream.add_object((1, 0), Object::Integer(7)).expect("add_object");
+
+        let content = object_stream.build_stream_content().expect("build_stream_content");
+        let stream = object_stream.to_stream_object().expect("to_stream_object");
+
+        assert!(stream.dict.get(b"Filter").is_err(), "stored() must not write a /Filter entry");
+        assert!(stream.dict.get(b"DecodeParms").is_err(), "stored() must not write a /DecodeParms entry");
+        assert_eq!(stream.content, content, "stored() must leave the content unencoded");
+    }
 }